@@ -2,9 +2,9 @@ use popsicle::psty_payload::{Receiver, ReceiverState};
 use popsicle::psty_utils::psty_large::{
     ReceiverMegabins,
 };
-use scuttlebutt::{AesRng, TrackChannel, SymChannel};
+use scuttlebutt::{AesRng};
 
-use match_compute::util;
+use match_compute::{channel::{Channel, Traffic}, tls::{Stream, Tls}, util};
 use std::{
     fs::{File},
     io::{Write, Read},
@@ -17,9 +17,9 @@ use std::{
 use bincode;
 use serde_json;
 
-fn client_protocol(mut channel: TrackChannel<SymChannel<TcpStream>>,
+fn client_protocol(mut channel: Channel<Stream>,
     path: &mut PathBuf, thread_id: usize, payload_size: usize)
-    ->(f64, f64){
+    -> Traffic{
     let start = SystemTime::now();
     println!("Receiver Thread {} Starting computation", thread_id);
     let mut rng = AesRng::new();
@@ -48,17 +48,18 @@ fn client_protocol(mut channel: TrackChannel<SymChannel<TcpStream>>,
     let p =  fancy_garbling::util::primes_with_width(payload_size as u32).len() + 1;
     let (acc, sum_weights) = psi.compute_circuit(p, payload_size, &mut megabins,&mut channel, &mut rng).unwrap();
 
+    let traffic = channel.traffic();
     println!(
         "Receiver Thread {} :: total circuit building & computation time: {} ms", thread_id,
         start.elapsed().unwrap().as_millis()
     );
     println!(
         "Receiver Thread {} :: total circuit building & computation communication (read): {:.2} Mb",thread_id,
-        channel.kilobits_read() / 1000.0
+        traffic.megabits_read()
     );
     println!(
         "Receiver Thread {} :: total circuit building & computation communication (write): {:.2} Mb",thread_id,
-        channel.kilobits_written() / 1000.0
+        traffic.megabits_written()
     );
 
     path.push("output_aggregate.txt");
@@ -78,20 +79,18 @@ fn client_protocol(mut channel: TrackChannel<SymChannel<TcpStream>>,
     file_aggregate.write(aggregate_json.as_bytes()).unwrap();
     file_sum_weights.write(sum_weights_json.as_bytes()).unwrap();
 
-    let total_read = channel.kilobits_read() / 1000.0;
-    let total_written = channel.kilobits_written() / 1000.0;
-    (total_read, total_written)
+    traffic
 }
 
 pub fn client_thread(path: &mut PathBuf, address: &str, thread_id: usize,
-                    payload_size: usize)
-    -> Result<(f64, f64), Error>{
+                    payload_size: usize, tls: &Tls)
+    -> Result<Traffic, Error>{
     let port_prefix = format!("{}{}", address,":300");
     let port = format!("{}{}", port_prefix, thread_id.to_string());
 
     match TcpStream::connect(port) {
         Ok(stream) => {
-            let channel = TrackChannel::new(SymChannel::new(stream));
+            let channel = tls.wrap(stream)?;
             Ok(client_protocol(channel, path, thread_id, payload_size))
         },
         Err(e) => {
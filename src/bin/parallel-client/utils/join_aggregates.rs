@@ -1,7 +1,7 @@
 use popsicle::psty_payload::{Receiver};
-use match_compute::util;
+use match_compute::{channel::{Channel, Traffic}, tls::{Stream, Tls}, util};
 use fancy_garbling::Wire;
-use scuttlebutt::{AesRng, SymChannel, TrackChannel};
+use scuttlebutt::{AesRng};
 
 use std::{
     fs::{File, write, read_to_string},
@@ -13,9 +13,9 @@ use std::{
 use serde_json;
 
 
-fn client_protocol(mut channel: TrackChannel<SymChannel<TcpStream>>,
+fn client_protocol(mut channel: Channel<Stream>,
     path:&mut PathBuf, nthreads: usize, _precision: u32, payload_size: usize)
-    -> (u128, f64, f64){
+    -> (u128, Traffic){
     let start = SystemTime::now();
     let mut rng = AesRng::new();
 
@@ -61,32 +61,31 @@ fn client_protocol(mut channel: TrackChannel<SymChannel<TcpStream>>,
 
     write(path_str, output_write).expect("Unable to write file");
 
+    let traffic = channel.traffic();
     println!(
         "Receiver :: total Joining threads results time: {} ms",
         start.elapsed().unwrap().as_millis()
     );
     println!(
         "Receiver :: total Joining threads results time (read): {:.2} Mb",
-        channel.kilobits_read() / 1000.0
+        traffic.megabits_read()
     );
     println!(
         "Receiver :: total Joining threads results time  (write): {:.2} Mb",
-        channel.kilobits_written() / 1000.0
+        traffic.megabits_written()
     );
 
-    let total_read = channel.kilobits_read() / 1000.0;
-    let total_written = channel.kilobits_written() / 1000.0;
-    (weighted_mean, total_read, total_written)
+    (weighted_mean, traffic)
 }
 
 pub fn join_aggregates(path:&mut PathBuf, address: &str,
-    nthreads: usize, precision: u32, payload_size: usize)
-    -> Result<(u128, f64, f64), Error>{
+    nthreads: usize, precision: u32, payload_size: usize, tls: &Tls)
+    -> Result<(u128, Traffic), Error>{
     let port_prefix = format!("{}{}", address,":3000");
 
     match TcpStream::connect(port_prefix) {
         Ok(stream) => {
-            let channel = TrackChannel::new(SymChannel::new(stream));
+            let channel = tls.wrap(stream)?;
             Ok(client_protocol(channel, path, nthreads, precision, payload_size))
         },
         Err(e) => {
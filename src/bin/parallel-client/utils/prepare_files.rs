@@ -1,8 +1,8 @@
 // Bucketize Data and Seperate it among threads
 use popsicle::psty_payload::{Receiver, ReceiverState};
-use match_compute::util;
+use match_compute::{channel::{Channel, Traffic}, tls::{Stream, Tls}, util};
 
-use scuttlebutt::{AesRng, Block512, TrackChannel, SymChannel};
+use scuttlebutt::{AesRng, Block512};
 
 use std::{
     fs::{File, create_dir_all},
@@ -15,9 +15,9 @@ use std::{
 
 use bincode;
 
-fn client_protocol(mut channel: TrackChannel<SymChannel<TcpStream>>, path: &mut PathBuf, nthread: usize,
+fn client_protocol(mut channel: Channel<Stream>, path: &mut PathBuf, nthread: usize,
                     megasize: usize, ids: &[Vec<u8>], payloads: &[Block512], client_padding: usize)
-                    ->(f64, f64){
+                    -> Traffic{
     let start = SystemTime::now();
 
     let mut rng = AesRng::new();
@@ -52,32 +52,31 @@ fn client_protocol(mut channel: TrackChannel<SymChannel<TcpStream>>, path: &mut
 
         path.pop();
     }
+    let traffic = channel.traffic();
     println!(
         "Receiver :: Bucketization time : {} ms",
         start.elapsed().unwrap().as_millis()
     );
     println!(
         "Receiver ::Bucketization time (read): {:.2} Mb",
-        channel.kilobits_read() / 1000.0
+        traffic.megabits_read()
     );
     println!(
         "Receiver :: Bucketization time (write): {:.2} Mb",
-        channel.kilobits_written() / 1000.0
+        traffic.megabits_written()
     );
 
-    let total_read = channel.kilobits_read() / 1000.0;
-    let total_written = channel.kilobits_written() / 1000.0;
-    (total_read, total_written)
+    traffic
 }
 
 pub fn prepare_files(path: &mut PathBuf, address: &str, nthread: usize, megasize: usize,
-                    ids: &[Vec<u8>], payloads: &[Block512], client_padding: usize)
-                    -> Result<(f64, f64), Error>{
+                    ids: &[Vec<u8>], payloads: &[Block512], client_padding: usize, tls: &Tls)
+                    -> Result<Traffic, Error>{
     let address = format!("{}{}", address,":3000");
 
     match TcpStream::connect(address) {
         Ok(stream) => {
-            let channel = TrackChannel::new(SymChannel::new(stream));
+            let channel = tls.wrap(stream)?;
             Ok(client_protocol(channel, path, nthread, megasize, ids, payloads, client_padding))
         },
         Err(e) => {
@@ -1,4 +1,4 @@
-use match_compute::util;
+use match_compute::{channel::Traffic, tls, util};
 
 use crate::utils::{
     prepare_files::prepare_files,
@@ -23,6 +23,7 @@ pub fn run_client(set_size: usize, id_size: usize, max_payload:u64,
     let parameters = util::parse_config(&mut path.clone());
     let (address, client_path, sleeptime, precision, nthread,
         megasize, client_padding, id_position, payload_position) = util::get_config_client(&parameters);
+    let tls = tls::client_tls(&parameters);
 
     let (ids, payloads) = if fake_data == true {
             // The ids & payloads are generated at random
@@ -34,8 +35,8 @@ pub fn run_client(set_size: usize, id_size: usize, max_payload:u64,
 
    // Bucketize the data and split into megabins that are distributed among threads
    path.push("bin/parallel-client/data");
-   let (read_init, written_init) = prepare_files(&mut path, &address, nthread, megasize,
-                                                &ids, &payloads, client_padding).unwrap();
+   let traffic_init = prepare_files(&mut path, &address, nthread, megasize,
+                                                &ids, &payloads, client_padding, &tls).unwrap();
 
    // Wait for the server to be done
    let duration = Duration::from_secs(sleeptime);
@@ -48,8 +49,9 @@ pub fn run_client(set_size: usize, id_size: usize, max_payload:u64,
     for i in 0..nthread {
         let mut path_thread = path.clone();
         let address_thread = address.clone();
+        let tls_thread = tls.clone();
        handle.push(thread::spawn(move || {
-           client_thread(&mut path_thread, &address_thread, i, payload_size).unwrap()
+           client_thread(&mut path_thread, &address_thread, i, payload_size, &tls_thread).unwrap()
        }));
    }
    let mut results = Vec::new();
@@ -58,21 +60,16 @@ pub fn run_client(set_size: usize, id_size: usize, max_payload:u64,
     }
    // The partial results are joined and the output is produced
     thread::sleep(duration);
-    let (_result_cardinality, read_final, written_final) = join_aggregates(&mut path, &address, nthread, precision, payload_size).unwrap();
+    let (_result_cardinality, traffic_final) = join_aggregates(&mut path, &address, nthread, precision, payload_size, &tls).unwrap();
 
-    let mut total_read = read_final + read_init;
-    let mut total_written = written_final + written_init;
-    for (r, w) in results{
-        total_read = total_read + r;
-        total_written = total_written + w;
-    }
+    let total = results.into_iter().sum::<Traffic>() + traffic_init + traffic_final;
 
     println!("TOTAL TIME in {} s",start.elapsed().unwrap().as_secs());
-    println!("TOTAL READ {} Mb",total_read);
-    println!("TOTAL WRITTEN {} Mb",total_written);
+    println!("TOTAL READ {} Mb",total.megabits_read());
+    println!("TOTAL WRITTEN {} Mb",total.megabits_written());
 
     // clear_results(&parameters,&mut path, &ids, &payloads, precision, fake_data);
     println!("Experiment done !");
     thread::sleep(duration);
-    (start.elapsed().unwrap().as_secs(), total_read, total_written)
+    (start.elapsed().unwrap().as_secs(), total.megabits_read(), total.megabits_written())
 }
@@ -23,7 +23,7 @@ pub fn test(ids_client: &[Vec<u8>], ids_server: &[Vec<u8>],
         let id_server: &[u8] = &ids_server[i];
         let id_server: [u8; 8] = id_server.try_into().unwrap();
         let id_server = u64::from_le_bytes(id_server);
-        let server_val = u64::from_le_bytes(payloads_server[i].prefix(8).try_into().unwrap());
+        let server_val = util::block512_to_u64(&payloads_server[i]);
 
         sever_elements.insert(
             id_server,
@@ -37,7 +37,7 @@ pub fn test(ids_client: &[Vec<u8>], ids_server: &[Vec<u8>],
         let id_client = u64::from_le_bytes(id_client);
 
         if sever_elements.contains_key(&id_client){
-            let client_val = u64::from_le_bytes(payloads_client[i].prefix(8).try_into().unwrap());
+            let client_val = util::block512_to_u64(&payloads_client[i]);
             weighted_payload = weighted_payload + client_val*sever_elements.get(&id_client).unwrap();
             sum_weights = sum_weights + sever_elements.get(&id_client).unwrap();
         }
@@ -1,14 +1,15 @@
 use popsicle::psty_payload::{Sender};
+use match_compute::{channel::Channel, tls::{Stream, Tls}};
 
 use fancy_garbling::{
     CrtBundle,
     Wire,
 };
-use scuttlebutt::{AesRng, SymChannel, TrackChannel};
+use scuttlebutt::{AesRng};
 
 use std::{
     fs::{read_to_string},
-    net::{TcpListener, TcpStream},
+    net::{TcpListener},
     time::SystemTime,
     path::PathBuf,
 };
@@ -21,7 +22,7 @@ fn wires_to_crt(v: &[Vec<Wire>])-> Vec<CrtBundle<Wire>>{
 }
 
 
-fn server_protocol(mut channel: TrackChannel<SymChannel<TcpStream>>, path:&mut PathBuf, nthreads: usize) {
+fn server_protocol(mut channel: Channel<Stream>, path:&mut PathBuf, nthreads: usize) {
     let start = SystemTime::now();
     let mut rng = AesRng::new();
 
@@ -56,21 +57,22 @@ fn server_protocol(mut channel: TrackChannel<SymChannel<TcpStream>>, path:&mut P
     let mut psi = Sender::init(&mut channel, &mut rng).unwrap();
     let _ = psi.join_circuits(&mut aggregates, &mut sum_weights, &path_delta, &mut channel,&mut rng);
 
+    let traffic = channel.traffic();
     println!(
         "Sender :: total Joining threads results time: {} ms",
         start.elapsed().unwrap().as_millis()
     );
     println!(
         "Sender :: total Joining threads results time (read): {:.2} Mb",
-        channel.kilobits_read() / 1000.0
+        traffic.megabits_read()
     );
     println!(
         "Sender :: total Joining threads results time  (write): {:.2} Mb",
-        channel.kilobits_written() / 1000.0
+        traffic.megabits_written()
     );
 }
 
-pub fn join_aggregates(path:&mut PathBuf, address: &str, nthreads: usize) {
+pub fn join_aggregates(path:&mut PathBuf, address: &str, nthreads: usize, tls: &Tls) {
     let port_prefix = format!("{}{}", address,":3000");
     println!("Server listening on {}", port_prefix);
     let listener = TcpListener::bind(port_prefix).unwrap();
@@ -80,9 +82,15 @@ pub fn join_aggregates(path:&mut PathBuf, address: &str, nthreads: usize) {
         match stream {
             Ok(stream) => {
                 println!("New connection: {}", stream.peer_addr().unwrap());
-                let channel = TrackChannel::new(SymChannel::new(stream));
-                server_protocol(channel, path, nthreads);
-                return;
+                match tls.wrap(stream) {
+                    Ok(channel) => {
+                        server_protocol(channel, path, nthreads);
+                        return;
+                    }
+                    Err(e) => {
+                        println!("TLS handshake failed: {}", e);
+                    }
+                }
             }
             Err(e) => {
                 println!("Error: {}", e);
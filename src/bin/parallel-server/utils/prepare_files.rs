@@ -1,14 +1,15 @@
 // Bucketize Data and Seperate it among threads
 use popsicle::psty_payload::{Sender, SenderState};
+use match_compute::{channel::Channel, tls::{Stream, Tls}};
 
-use scuttlebutt::{AesRng, Block512, TrackChannel, SymChannel};
+use scuttlebutt::{AesRng, Block512};
 extern crate fancy_garbling;
 use fancy_garbling::Wire;
 
 use std::{
     fs::{File, create_dir_all},
     io::{Write},
-    net::{TcpListener, TcpStream},
+    net::{TcpListener},
     collections::HashMap,
     time::SystemTime,
     path::PathBuf,
@@ -25,7 +26,7 @@ pub fn generate_deltas() -> HashMap<u16, Wire> {
     deltas
 }
 
-fn server_protocol(mut stream: TrackChannel<SymChannel<TcpStream>>, path: &mut PathBuf, nthread: usize,
+fn server_protocol(mut stream: Channel<Stream>, path: &mut PathBuf, nthread: usize,
                     ids: &[Vec<u8>], payloads: &[Block512], payload_size: usize){
     let start = SystemTime::now();
 
@@ -75,23 +76,24 @@ fn server_protocol(mut stream: TrackChannel<SymChannel<TcpStream>>, path: &mut P
         path.pop();
     }
 
+    let traffic = stream.traffic();
     println!(
         "Sender :: Bucketization time: {} ms",
         start.elapsed().unwrap().as_millis()
     );
     println!(
         "Sender :: Bucketization time (read): {:.2} Mb",
-        stream.kilobits_read() / 1000.0
+        traffic.megabits_read()
     );
     println!(
         "Sender :: Bucketization time  (write): {:.2} Mb",
-        stream.kilobits_written() / 1000.0
+        traffic.megabits_written()
     );
 
 }
 
 pub fn prepare_files(path: &mut PathBuf, address: &str, nthread: usize,
-    ids: &[Vec<u8>], payloads: &[Block512], payload_size: usize) {
+    ids: &[Vec<u8>], payloads: &[Block512], payload_size: usize, tls: &Tls) {
     let address = format!("{}{}", address,":3000");
     println!("Server listening on {}", address);
     let listener = TcpListener::bind(address).unwrap();
@@ -100,10 +102,15 @@ pub fn prepare_files(path: &mut PathBuf, address: &str, nthread: usize,
         match stream {
             Ok(stream) => {
                 println!("New connection: {}", stream.peer_addr().unwrap());
-                    let channel = TrackChannel::new(SymChannel::new(stream));
-                    server_protocol(channel, path, nthread, ids, payloads, payload_size);
-                    return;
-
+                match tls.wrap(stream) {
+                    Ok(channel) => {
+                        server_protocol(channel, path, nthread, ids, payloads, payload_size);
+                        return;
+                    }
+                    Err(e) => {
+                        println!("TLS handshake failed: {}", e);
+                    }
+                }
             }
             Err(e) => {
                 println!("Error: {}", e);
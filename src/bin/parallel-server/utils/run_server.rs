@@ -1,4 +1,4 @@
-use match_compute::util;
+use match_compute::{tls, util};
 
 use crate::utils::{
     prepare_files::prepare_files,
@@ -15,6 +15,7 @@ pub fn run_server(set_size: usize, id_size: usize, max_payload:u64, payload_size
     let parameters = util::parse_config(&mut path.clone());
     let (address, server_path, nthread, id_position, payload_position) =
                                         util::get_config_sever(&parameters);
+    let tls = tls::server_tls(&parameters);
 
     let(ids, payloads) = if fake_data == true {
             // The ids & payloads are generated at random
@@ -27,7 +28,7 @@ pub fn run_server(set_size: usize, id_size: usize, max_payload:u64, payload_size
 
    // Bucketize the data and split into megabins that are distributed among threads
     path.push("bin/parallel-server/data");
-    prepare_files(&mut path, &address, nthread, &ids, &payloads, payload_size);
+    prepare_files(&mut path, &address, nthread, &ids, &payloads, payload_size, &tls);
 
     // Each thread handles its own megabins and speaks to the appropriate other party thread
     // via a dedicated port. The partial results of this computation are garbled and
@@ -36,8 +37,9 @@ pub fn run_server(set_size: usize, id_size: usize, max_payload:u64, payload_size
     for i in 0..nthread {
         let mut path_thread = path.clone();
         let address_thread = address.clone();
+        let tls_thread = tls.clone();
        handle.push(thread::spawn(move || {
-           server_thread(&mut path_thread, &address_thread, i, payload_size);
+           server_thread(&mut path_thread, &address_thread, i, payload_size, &tls_thread);
        }));
    }
    for thread in handle {
@@ -45,7 +47,7 @@ pub fn run_server(set_size: usize, id_size: usize, max_payload:u64, payload_size
     }
 
     // The partial results are joined and the output is produced
-    join_aggregates(&mut path, &address, nthread);
+    join_aggregates(&mut path, &address, nthread, &tls);
 
     println!("Experiments done !");
 }
@@ -4,7 +4,8 @@ use popsicle::psty_utils::psty_large::{
     SenderMegabins,
 };
 
-use scuttlebutt::{AesRng, TrackChannel, SymChannel};
+use scuttlebutt::{AesRng};
+use match_compute::{channel::Channel, tls::{Stream, Tls}};
 
 use fancy_garbling::{
     CrtBundle,
@@ -14,7 +15,7 @@ use fancy_garbling::{
 use std::{
     fs::{File},
     io::{Write, Read},
-    net::{TcpListener, TcpStream},
+    net::{TcpListener},
     time::SystemTime,
     path::PathBuf,
 };
@@ -26,7 +27,7 @@ fn crt_to_wires(v: &[CrtBundle<Wire>])-> Vec<Vec<Wire>>{
      .map(|c| c.wires().to_vec()).collect()
 }
 
-fn server_protocol(mut stream: TrackChannel<SymChannel<TcpStream>>, path:&mut PathBuf,
+fn server_protocol(mut stream: Channel<Stream>, path:&mut PathBuf,
             thread_id: usize, payload_size: usize) {
     let start = SystemTime::now();
     println!("Sender Thread {} Starting computation", thread_id);
@@ -60,17 +61,18 @@ fn server_protocol(mut stream: TrackChannel<SymChannel<TcpStream>>, path:&mut Pa
     let p =  fancy_garbling::util::primes_with_width(payload_size as u32).len() + 1;
     let (acc, sum_weights) = psi.compute_circuit(p, payload_size, &mut megabins, &path_delta, &mut stream, &mut rng).unwrap();
 
+    let traffic = stream.traffic();
     println!(
         "Sender Thread {} :: total circuit building & computation time: {} ms", thread_id,
         start.elapsed().unwrap().as_millis()
     );
     println!(
         "Sender Thread {} :: total circuit building & computation communication (read): {:.2} Mb",thread_id,
-        stream.kilobits_read() / 1000.0
+        traffic.megabits_read()
     );
     println!(
         "Sender Thread {} :: total circuit building & computation communication (write): {:.2} Mb",thread_id,
-        stream.kilobits_written() / 1000.0
+        traffic.megabits_written()
     );
     path.push("output_aggregate.txt");
     let path_str = path.clone().into_os_string().into_string().unwrap();
@@ -89,7 +91,7 @@ fn server_protocol(mut stream: TrackChannel<SymChannel<TcpStream>>, path:&mut Pa
     file_sum_weights.write(sum_weights_json.as_bytes()).unwrap();
 }
 
-pub fn server_thread(path:&mut PathBuf, address: &str, thread_id: usize, payload_size: usize) {
+pub fn server_thread(path:&mut PathBuf, address: &str, thread_id: usize, payload_size: usize, tls: &Tls) {
     let port_prefix = format!("{}{}", address,":300");
     let port = format!("{}{}", port_prefix, thread_id.to_string());
     println!("Server listening on {}", port);
@@ -99,9 +101,15 @@ pub fn server_thread(path:&mut PathBuf, address: &str, thread_id: usize, payload
         match stream {
             Ok(stream) => {
                 println!("New connection: {}", stream.peer_addr().unwrap());
-                let channel = TrackChannel::new(SymChannel::new(stream));
-                server_protocol(channel, path, thread_id, payload_size);
-                return;
+                match tls.wrap(stream) {
+                    Ok(channel) => {
+                        server_protocol(channel, path, thread_id, payload_size);
+                        return;
+                    }
+                    Err(e) => {
+                        println!("TLS handshake failed: {}", e);
+                    }
+                }
             }
             Err(e) => {
                 println!("Error: {}", e);
@@ -1,6 +1,6 @@
 // A simple single threaded example of PSI with match and compute
 mod utils;
-use match_compute::util;
+use match_compute::{tls, util};
 use crate::utils::run_client::run_client;
 
 
@@ -10,10 +10,12 @@ fn main() {
     let parameters = util::parse_config(&mut path.clone());
     let (address, set_size, id_size, payload_size, max_payload, _, _) = util::get_config_experiments(&parameters);
 
-    let (time, read, written) = run_client(&address, set_size, id_size, max_payload, payload_size).unwrap();
+    let tls = tls::client_tls(&parameters);
+
+    let (time, traffic) = run_client(&address, set_size, id_size, max_payload, payload_size, &tls).unwrap();
 
     println!("TOTAL TIME in {} ms",time);
-    println!("TOTAL READ {} Mb",read);
-    println!("TOTAL WRITTEN {} Mb",written);
+    println!("TOTAL READ {} Mb",traffic.megabits_read());
+    println!("TOTAL WRITTEN {} Mb",traffic.megabits_written());
 
 }
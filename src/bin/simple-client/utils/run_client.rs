@@ -1,9 +1,9 @@
 // A simple single threaded example of PSI with match and compute
 
-use match_compute::util;
+use match_compute::{channel::{Channel, Traffic}, tls::{Stream, Tls}, util};
 use popsicle::psty_payload::{Receiver};
 
-use scuttlebutt::{AesRng, TrackChannel, SymChannel};
+use scuttlebutt::{AesRng};
 
 use std::{
     net::{TcpStream},
@@ -12,7 +12,7 @@ use std::{
 };
 
 fn client_protocol(set_size: usize, id_size: usize, max_payload: u64, payload_size: usize,
-                    mut channel: TrackChannel<SymChannel<TcpStream>>)-> (u128, f64, f64){
+                    mut channel: Channel<Stream>)-> (u128, Traffic){
     let start = SystemTime::now();
     let mut rng = AesRng::new();
     let (receiver_inputs, payloads) = util::generate_dummy_data(set_size, id_size, max_payload);
@@ -22,15 +22,16 @@ fn client_protocol(set_size: usize, id_size: usize, max_payload: u64, payload_si
     let _weighted_mean = psi
         .full_protocol(&receiver_inputs, &payloads, payload_size, &mut channel, &mut rng)
         .unwrap();
-    (start.elapsed().unwrap().as_millis(), channel.kilobits_read() / 1000.0, channel.kilobits_written() / 1000.0)
+    (start.elapsed().unwrap().as_millis(), channel.traffic())
 }
 
-pub fn run_client(address: &str, set_size: usize, id_size: usize, max_payload: u64, payload_size: usize)
-        ->Result<(u128, f64, f64), Error>{
+pub fn run_client(address: &str, set_size: usize, id_size: usize, max_payload: u64, payload_size: usize,
+                    tls: &Tls)
+        ->Result<(u128, Traffic), Error>{
     let address = format!("{}{}", address,":3000");
     match TcpStream::connect(address) {
         Ok(stream) => {
-            let channel = TrackChannel::new(SymChannel::new(stream));
+            let channel = tls.wrap(stream)?;
             Ok(client_protocol(set_size, id_size, max_payload, payload_size, channel))
         },
         Err(e) => {
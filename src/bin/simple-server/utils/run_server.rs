@@ -1,15 +1,15 @@
 // A simple single threaded example of PSI with match and compute
-use match_compute::util;
+use match_compute::{channel::Channel, tls::{Stream, Tls}, util};
 use popsicle::psty_payload::{Sender};
 
-use scuttlebutt::{AesRng, TrackChannel, SymChannel};
+use scuttlebutt::{AesRng};
 
 use std::{
-    net::{TcpListener, TcpStream},
+    net::{TcpListener},
 };
 
 fn server_protocol(set_size: usize, id_size: usize, max_payload: u64, payload_size: usize,
-                    mut stream: TrackChannel<SymChannel<TcpStream>>){
+                    mut stream: Channel<Stream>){
 
     let mut rng = AesRng::new();
     let (sender_inputs, payload) = util::generate_dummy_data(set_size, id_size, max_payload);
@@ -21,7 +21,8 @@ fn server_protocol(set_size: usize, id_size: usize, max_payload: u64, payload_si
 }
 
 
-pub fn run_server(address: &str, set_size: usize, id_size: usize, max_payload: u64, payload_size: usize){
+pub fn run_server(address: &str, set_size: usize, id_size: usize, max_payload: u64, payload_size: usize,
+                    tls: &Tls){
     let address = format!("{}{}", address,":3000");
     let listener = TcpListener::bind(address).unwrap();
     // accept connections and process them, spawning a new thread for each one
@@ -30,10 +31,15 @@ pub fn run_server(address: &str, set_size: usize, id_size: usize, max_payload: u
         match stream {
             Ok(stream) => {
                 println!("New connection: {}", stream.peer_addr().unwrap());
-                    let channel = TrackChannel::new(SymChannel::new(stream));
-                    server_protocol(set_size, id_size, max_payload, payload_size, channel);
-                    return;
-
+                match tls.wrap(stream) {
+                    Ok(channel) => {
+                        server_protocol(set_size, id_size, max_payload, payload_size, channel);
+                        return;
+                    }
+                    Err(e) => {
+                        println!("TLS handshake failed: {}", e);
+                    }
+                }
             }
             Err(e) => {
                 println!("Error: {}", e);
@@ -0,0 +1,139 @@
+// Channel between the two parties that keeps track of the traffic it carries.
+use scuttlebutt::AbstractChannel;
+
+use std::{
+    cell::{Cell, RefCell},
+    io::{Read, Result, Write},
+    iter::Sum,
+    ops::Add,
+    rc::Rc,
+};
+
+/// Bytes read and written on a connection.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Traffic {
+    pub read: usize,
+    pub written: usize,
+}
+
+impl Traffic {
+    pub fn megabits_read(&self) -> f64 {
+        (self.read * 8) as f64 / 1_000_000.0
+    }
+
+    pub fn megabits_written(&self) -> f64 {
+        (self.written * 8) as f64 / 1_000_000.0
+    }
+}
+
+impl Add for Traffic {
+    type Output = Traffic;
+
+    fn add(self, other: Traffic) -> Traffic {
+        Traffic {
+            read: self.read + other.read,
+            written: self.written + other.written,
+        }
+    }
+}
+
+impl Sum for Traffic {
+    fn sum<I: Iterator<Item = Traffic>>(iter: I) -> Traffic {
+        iter.fold(Traffic::default(), Add::add)
+    }
+}
+
+/// A `Read + Write` stream that counts every byte going through it.
+pub struct Counted<S> {
+    stream: S,
+    traffic: Rc<Cell<Traffic>>,
+}
+
+impl<S> Counted<S> {
+    pub fn new(stream: S) -> Self {
+        Counted {
+            stream,
+            traffic: Rc::new(Cell::new(Traffic::default())),
+        }
+    }
+
+    /// Handle on the counts, still updated after the stream is moved.
+    pub fn counter(&self) -> Rc<Cell<Traffic>> {
+        self.traffic.clone()
+    }
+}
+
+impl<S: Read> Read for Counted<S> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.stream.read(buf)?;
+        let mut traffic = self.traffic.get();
+        traffic.read += n;
+        self.traffic.set(traffic);
+        Ok(n)
+    }
+}
+
+impl<S: Write> Write for Counted<S> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.stream.write(buf)?;
+        let mut traffic = self.traffic.get();
+        traffic.written += n;
+        self.traffic.set(traffic);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.stream.flush()
+    }
+}
+
+/// Channel over any `Read + Write` stream, reporting the bytes it moved.
+pub struct Channel<S> {
+    stream: Rc<RefCell<S>>,
+    traffic: Rc<Cell<Traffic>>,
+}
+
+impl<S: Read + Write> Channel<Counted<S>> {
+    pub fn new(stream: S) -> Self {
+        let stream = Counted::new(stream);
+        let traffic = stream.counter();
+        Channel::with_counter(stream, traffic)
+    }
+}
+
+impl<S> Channel<S> {
+    /// Channel over a stream whose bytes are counted further down, such as
+    /// the socket underneath a TLS session.
+    pub fn with_counter(stream: S, traffic: Rc<Cell<Traffic>>) -> Self {
+        Channel {
+            stream: Rc::new(RefCell::new(stream)),
+            traffic,
+        }
+    }
+
+    /// Traffic so far, shared with every clone of this channel.
+    pub fn traffic(&self) -> Traffic {
+        self.traffic.get()
+    }
+}
+
+impl<S: Read + Write> AbstractChannel for Channel<S> {
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        self.stream.borrow_mut().read_exact(bytes)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.stream.borrow_mut().write_all(bytes)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.stream.borrow_mut().flush()
+    }
+
+    fn clone(&self) -> Self {
+        Channel {
+            stream: self.stream.clone(),
+            traffic: self.traffic.clone(),
+        }
+    }
+}
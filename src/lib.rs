@@ -1 +1,3 @@
+pub mod channel;
+pub mod tls;
 pub mod util;
@@ -0,0 +1,176 @@
+// TLS transport between the client and the server.
+// Both parties authenticate each other with certificates signed by a shared CA.
+use crate::{
+    channel::{Channel, Counted},
+    util,
+};
+use rustls::{
+    server::AllowAnyAuthenticatedClient,
+    Certificate,
+    ClientConfig,
+    ClientConnection,
+    PrivateKey,
+    RootCertStore,
+    ServerConfig,
+    ServerConnection,
+    ServerName,
+    StreamOwned,
+};
+use rustls_pemfile::{certs, pkcs8_private_keys, rsa_private_keys};
+use std::convert::TryFrom;
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, Error, Read, Result, Write},
+    net::TcpStream,
+    sync::Arc,
+};
+
+/// A connection to the other party, encrypted when TLS is enabled.
+pub enum Stream {
+    Plain(Counted<TcpStream>),
+    Client(StreamOwned<ClientConnection, Counted<TcpStream>>),
+    Server(StreamOwned<ServerConnection, Counted<TcpStream>>),
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self {
+            Stream::Plain(s) => s.read(buf),
+            Stream::Client(s) => s.read(buf),
+            Stream::Server(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        match self {
+            Stream::Plain(s) => s.write(buf),
+            Stream::Client(s) => s.write(buf),
+            Stream::Server(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            Stream::Plain(s) => s.flush(),
+            Stream::Client(s) => s.flush(),
+            Stream::Server(s) => s.flush(),
+        }
+    }
+}
+
+/// How a party sets up its connections. Cheap to clone into worker threads.
+#[derive(Clone)]
+pub enum Tls {
+    Disabled,
+    Client(Arc<ClientConfig>, ServerName),
+    Server(Arc<ServerConfig>),
+}
+
+impl Tls {
+    /// Wrap a freshly connected or accepted socket into a channel and
+    /// complete the TLS handshake, so certificate errors surface here rather
+    /// than in the middle of the protocol. Traffic is counted on the socket,
+    /// so it includes the TLS handshake and record overhead.
+    pub fn wrap(&self, stream: TcpStream) -> Result<Channel<Stream>> {
+        let mut stream = Counted::new(stream);
+        let traffic = stream.counter();
+        let stream = match self {
+            Tls::Disabled => Stream::Plain(stream),
+            Tls::Client(config, server_name) => {
+                let mut conn = ClientConnection::new(config.clone(), server_name.clone())
+                    .map_err(Error::other)?;
+                while conn.is_handshaking() || conn.wants_write() {
+                    conn.complete_io(&mut stream)?;
+                }
+                Stream::Client(StreamOwned::new(conn, stream))
+            }
+            Tls::Server(config) => {
+                let mut conn = ServerConnection::new(config.clone()).map_err(Error::other)?;
+                while conn.is_handshaking() || conn.wants_write() {
+                    conn.complete_io(&mut stream)?;
+                }
+                Stream::Server(StreamOwned::new(conn, stream))
+            }
+        };
+        Ok(Channel::with_counter(stream, traffic))
+    }
+}
+
+fn open_pem(path: &str, key: &str) -> BufReader<File> {
+    let file = File::open(path).unwrap_or_else(|e| panic!("{}: cannot open {}: {}", key, path, e));
+    BufReader::new(file)
+}
+
+fn load_roots(path: &str) -> RootCertStore {
+    let ders = certs(&mut open_pem(path, "tls_ca"))
+        .unwrap_or_else(|_| panic!("tls_ca: {} is not a valid PEM file", path));
+    let mut roots = RootCertStore::empty();
+    let (valid, _) = roots.add_parsable_certificates(&ders);
+    if valid == 0 {
+        panic!("tls_ca: no usable CA certificate in {}", path);
+    }
+    roots
+}
+
+fn load_certs(path: &str) -> Vec<Certificate> {
+    let ders = certs(&mut open_pem(path, "tls_cert"))
+        .unwrap_or_else(|_| panic!("tls_cert: {} is not a valid PEM file", path));
+    if ders.is_empty() {
+        panic!("tls_cert: no certificate in {}", path);
+    }
+    ders.into_iter().map(Certificate).collect()
+}
+
+// Accepts both PKCS#8 (BEGIN PRIVATE KEY) and PKCS#1 (BEGIN RSA PRIVATE KEY) keys.
+fn load_key(path: &str) -> PrivateKey {
+    let mut keys = pkcs8_private_keys(&mut open_pem(path, "tls_key"))
+        .unwrap_or_else(|_| panic!("tls_key: {} is not a valid PEM file", path));
+    if keys.is_empty() {
+        keys = rsa_private_keys(&mut open_pem(path, "tls_key"))
+            .unwrap_or_else(|_| panic!("tls_key: {} is not a valid PEM file", path));
+    }
+    if keys.is_empty() {
+        panic!("tls_key: no PKCS#8 or RSA private key in {}", path);
+    }
+    PrivateKey(keys.remove(0))
+}
+
+/// Client side TLS settings from the configuration file.
+pub fn client_tls(parameters: &HashMap<String, String>) -> Tls {
+    let (ca_path, cert_path, key_path, domain) = match util::get_config_tls(parameters) {
+        Some(settings) => settings,
+        None => return Tls::Disabled,
+    };
+    let domain = domain.expect("tls is enabled but tls_domain is missing");
+    let server_name = ServerName::try_from(domain.as_str()).unwrap_or_else(|_| {
+        panic!(
+            "tls_domain: {} is not a valid DNS name or IP address",
+            domain
+        )
+    });
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(load_roots(&ca_path))
+        .with_client_auth_cert(load_certs(&cert_path), load_key(&key_path))
+        .expect("tls_cert and tls_key are not a matching certificate and key");
+    Tls::Client(Arc::new(config), server_name)
+}
+
+/// Server side TLS settings from the configuration file.
+/// Clients must present a certificate signed by the configured CA.
+pub fn server_tls(parameters: &HashMap<String, String>) -> Tls {
+    let (ca_path, cert_path, key_path, _) = match util::get_config_tls(parameters) {
+        Some(settings) => settings,
+        None => return Tls::Disabled,
+    };
+    let config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_client_cert_verifier(AllowAnyAuthenticatedClient::new(load_roots(&ca_path)).boxed())
+        .with_single_cert(load_certs(&cert_path), load_key(&key_path))
+        .expect("tls_cert and tls_key are not a matching certificate and key");
+    Tls::Server(Arc::new(config))
+}
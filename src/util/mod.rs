@@ -3,6 +3,7 @@ use std::{
     fs::{File, read_to_string},
     io::{BufRead, BufReader, stdin, stdout, Read, Write},
     collections::HashMap,
+    convert::TryInto,
     path::PathBuf,
 };
 
@@ -14,16 +15,22 @@ use fancy_garbling::{
 use scuttlebutt::{AesRng, Block512};
 use serde_json;
 
+/// Payload block holding `value` in its first 8 bytes (little-endian).
+pub fn u64_to_block512(value: u64) -> Block512 {
+    let mut res_block = [0 as u8; 64];
+    res_block[..8].copy_from_slice(&value.to_le_bytes());
+    Block512::from(res_block)
+}
+
+/// Inverse of `u64_to_block512`: the integer payload in the first 8 bytes.
+pub fn block512_to_u64(block: &Block512) -> u64 {
+    u64::from_le_bytes(block.prefix(8).try_into().unwrap())
+}
+
 pub fn int_vec_block512(values: Vec<u64>) -> Vec<Block512> {
     values.into_iter()
-          .map(|item|{
-            let value_bytes = item.to_le_bytes();
-            let mut res_block = [0 as u8; 64];
-            for i in 0..8{
-                res_block[i] = value_bytes[i];
-            }
-            Block512::from(res_block)
-         }).collect()
+          .map(u64_to_block512)
+          .collect()
 }
 pub fn rand_u64_vec<RNG: CryptoRng + Rng>(n: usize, modulus: u64, rng: &mut RNG) -> Vec<u64>{
     (0..n).map(|_| rng.gen::<u64>()%modulus).collect()
@@ -70,6 +77,7 @@ pub fn pad_data<RNG: CryptoRng + Rng>(ids: &[Vec<u8>], payloads: &[Block512],
     }
     let mut ids_padded = ids.to_vec();
     let mut payloads_padded = payloads.to_vec();
+    let zero_payload = u64_to_block512(0);
 
     for _i in 0..client_padding{
         let mut new_id = rng.gen::<u64>();
@@ -77,7 +85,7 @@ pub fn pad_data<RNG: CryptoRng + Rng>(ids: &[Vec<u8>], payloads: &[Block512],
             new_id = rng.gen::<u64>();
         }
         ids_padded.push(new_id.to_le_bytes().to_vec());
-        payloads_padded.push(Block512::from([0 as u8; 64]));
+        payloads_padded.push(zero_payload);
     }
     (ids_padded, payloads_padded)
 }
@@ -177,11 +185,17 @@ pub fn parse_config(path_config: &mut PathBuf) -> HashMap<String, String>{
     path_config.push("config/configuration.txt");
     let absolute_path = path_config.clone().into_os_string().into_string().unwrap();
     let configuration = File::open(absolute_path).unwrap();
-    let buffer = BufReader::new(configuration).lines();
+    parse_parameters(BufReader::new(configuration))
+}
+
+/// `key: value` pairs from a configuration, one per line.
+pub fn parse_parameters<R: BufRead>(reader: R) -> HashMap<String, String>{
+    let buffer = reader.lines();
     let mut parameters = HashMap::new();
     for line in buffer.enumerate(){
         let read_line =  line.1.unwrap();
-        if !read_line.is_empty(){
+        // Lines starting with '#' are comments
+        if !read_line.is_empty() && !read_line.starts_with('#'){
             let line_split = read_line.split(": ").map(|item| item.to_string()).collect::<Vec<String>>();
             parameters.insert(line_split[0].clone(), line_split[1].clone());
         }
@@ -233,6 +247,25 @@ pub fn get_config_client(parameters: &HashMap<String, String>)->
     (address, client_path, sleeptime, precision, nthread, megasize, client_padding, id_position, payload_position)
 }
 
+/// TLS is off unless the configuration sets `tls: true`, in which case the
+/// settings are returned as (ca, cert, key, domain). Only the client needs
+/// `tls_domain`, so it may be missing.
+pub fn get_config_tls(parameters: &HashMap<String, String>)->
+                                    Option<(String, String, String, Option<String>)>{
+    let enabled = parameters.get("tls").is_some_and(|v| {
+        v.parse::<bool>().unwrap_or_else(|_| panic!("tls: expected true or false, got {}", v))
+    });
+    if !enabled {
+        return None;
+    }
+    let ca_path = parameters.get("tls_ca").expect("tls is enabled but tls_ca is missing").to_owned();
+    let cert_path = parameters.get("tls_cert").expect("tls is enabled but tls_cert is missing").to_owned();
+    let key_path = parameters.get("tls_key").expect("tls is enabled but tls_key is missing").to_owned();
+    let domain = parameters.get("tls_domain").cloned();
+
+    Some((ca_path, cert_path, key_path, domain))
+}
+
 // Taken from:
 // https://www.reddit.com/r/rust/comments/8tfyof/noob_question_pause/e177530?utm_source=share&utm_medium=web2x&context=3
 fn _pause() {
@@ -248,3 +281,35 @@ fn _windows_hang_executable(){
         loop { }
     }));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters(config: &str) -> HashMap<String, String> {
+        parse_parameters(config.as_bytes())
+    }
+
+    #[test]
+    fn tls_missing_is_disabled() {
+        assert!(get_config_tls(&parameters("address: 127.0.0.1:3000\n")).is_none());
+    }
+
+    #[test]
+    fn tls_false_is_disabled() {
+        assert!(get_config_tls(&parameters("tls: false\ntls_ca: ca.pem\n")).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "tls is enabled but tls_key is missing")]
+    fn tls_true_needs_every_path() {
+        get_config_tls(&parameters("tls: true\ntls_ca: ca.pem\ntls_cert: cert.pem\n"));
+    }
+
+    #[test]
+    fn commented_out_line_is_skipped() {
+        let parameters = parameters("# tls: true\ntls_domain: localhost\n");
+        assert!(!parameters.contains_key("# tls"));
+        assert!(get_config_tls(&parameters).is_none());
+    }
+}